@@ -0,0 +1,648 @@
+use std::{collections::HashMap, convert::TryFrom, error::Error, fmt, iter::Peekable, slice::Iter};
+
+/// Define the tokens that the input string can have.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Plus,               // a => +
+    Dash,               // b => -
+    Star,               // c => *
+    Slash,              // d => /
+    LeftParen,          // e => (
+    RightParen,         // f => )
+    Caret,              // g => ^
+    Equals,             // = => assignment
+    Number(f64),        // regular number
+    Identifier(String), // variable name
+    End,                // end of the expression
+}
+
+/// Define the arithmetic operations that can be performed.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Multiply,
+    Divide,
+    Subtract,
+    Negative,
+    Power,
+}
+
+/// Define the conversion from tokens to operators
+/// by implementing the TryFrom trait.
+impl TryFrom<Token> for Operator {
+    type Error = &'static str;
+
+    fn try_from(token: Token) -> Result<Self, Self::Error> {
+        match token {
+            Token::Plus => Ok(Operator::Add),
+            Token::Dash => Ok(Operator::Subtract),
+            Token::Star => Ok(Operator::Multiply),
+            Token::Slash => Ok(Operator::Divide),
+            Token::Caret => Ok(Operator::Power),
+            _ => Err("Wrong operator"),
+        }
+    }
+}
+
+/// The symbol used to render an operator back to infix notation.
+fn operator_symbol(op: &Operator) -> &'static str {
+    match op {
+        Operator::Add => "+",
+        Operator::Subtract => "-",
+        Operator::Multiply => "*",
+        Operator::Divide => "/",
+        Operator::Power => "^",
+        Operator::Negative => "-",
+    }
+}
+
+/// Binding strength used by the `Display` pretty-printer. It matches the
+/// order of the parser's grammar levels (`term`, `factor`, `power`, then
+/// unary minus in `primary_expression`), which do not call it. Higher binds
+/// tighter. Leaves (numbers, variables) always outrank every operator.
+fn precedence(op: &Operator) -> u8 {
+    match op {
+        Operator::Add | Operator::Subtract => 1,
+        Operator::Multiply | Operator::Divide => 2,
+        Operator::Power => 3,
+        Operator::Negative => 4,
+    }
+}
+
+fn is_right_associative(op: &Operator) -> bool {
+    matches!(op, Operator::Power)
+}
+
+/// Define the expressions that you can find.
+/// There are five main expressions:
+///     - A number (like 7)
+///     - A unary operation (like -7, which is -1 * 7)
+///     - A binary operation (like 3 * 4)
+///     - A variable reference (like x)
+///     - An assignment (like x = 3 + 4), which binds a variable
+///       and also evaluates to the assigned value
+///
+/// With these expressions you can define the abstract
+/// syntax tree.
+#[derive(Debug, PartialEq)]
+pub enum Expression {
+    Number(f64),
+    Unary(Operator, Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Variable(String),
+    Assign(String, Box<Expression>),
+}
+
+/// Evaluate the expressions that you find based on the
+/// type of operation that [the expression] defines.
+/// The actual symbol that represents an operator will be
+/// defined in the `lexicon` function.
+///
+/// Variables are looked up and bound in `env`, which is threaded
+/// through the whole recursive evaluation rather than kept on `self`,
+/// so the same environment can be reused across separate `eval` calls.
+impl Expression {
+    pub fn eval(&mut self, env: &mut HashMap<String, f64>) -> Result<f64, SyntaxError> {
+        match self {
+            Expression::Number(n) => Ok(*n),
+            Expression::Unary(_negative, expr) => Ok(-expr.eval(env)?),
+            Expression::Binary(Operator::Add, expr1, expr2) => {
+                Ok(expr1.eval(env)? + expr2.eval(env)?)
+            }
+            Expression::Binary(Operator::Subtract, expr1, expr2) => {
+                Ok(expr1.eval(env)? - expr2.eval(env)?)
+            }
+            Expression::Binary(Operator::Multiply, expr1, expr2) => {
+                Ok(expr1.eval(env)? * expr2.eval(env)?)
+            }
+            Expression::Binary(Operator::Divide, expr1, expr2) => {
+                let dividend = expr1.eval(env)?;
+                let divisor = expr2.eval(env)?;
+                if divisor == 0.0 {
+                    return Err(SyntaxError::new_eval_error("Division by zero".to_string()));
+                }
+                Ok(dividend / divisor)
+            }
+            Expression::Binary(Operator::Power, base, exp) => {
+                let base = base.eval(env)?;
+                let exp = exp.eval(env)?;
+                let result = base.powf(exp);
+                if !result.is_finite() {
+                    return Err(SyntaxError::new_eval_error(format!(
+                        "{} raised to the power of {} is not finite",
+                        base, exp
+                    )));
+                }
+                Ok(result)
+            }
+            Expression::Variable(name) => env
+                .get(name)
+                .copied()
+                .ok_or_else(|| SyntaxError::new_eval_error(format!("Unknown variable {}", name))),
+            Expression::Assign(name, expr) => {
+                let value = expr.eval(env)?;
+                env.insert(name.clone(), value);
+                Ok(value)
+            }
+            _ => Err(SyntaxError::new_eval_error(format!(
+                "Cannot evaluate expression {:?}",
+                self
+            ))),
+        }
+    }
+}
+
+/// Reconstructs an `Expression` as a conventional infix string using
+/// `+ - * / ^`, e.g. the AST for `3a2c4` prints as `3 + 2 * 4`. This is
+/// for human-readable output only: `lexicon` understands the `a`-`g`/`=`
+/// encoding, not these symbols, so the result does not round-trip back
+/// through this crate's own parser. Parentheses are only added where
+/// needed to preserve the tree's grouping.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", render(self))
+    }
+}
+
+/// Leaves and unary expressions never need parenthesising; binary
+/// expressions are only as tight-binding as their operator.
+fn expr_precedence(expr: &Expression) -> u8 {
+    match expr {
+        Expression::Number(_) | Expression::Variable(_) => u8::MAX,
+        Expression::Unary(..) => precedence(&Operator::Negative),
+        Expression::Binary(op, ..) => precedence(op),
+        Expression::Assign(..) => 0,
+    }
+}
+
+fn render(expr: &Expression) -> String {
+    match expr {
+        Expression::Number(n) if n.fract() == 0.0 && n.is_finite() => format!("{}", *n as i64),
+        Expression::Number(n) => n.to_string(),
+        Expression::Variable(name) => name.clone(),
+        Expression::Unary(_, inner) => {
+            format!(
+                "-{}",
+                render_operand(inner, precedence(&Operator::Negative), false, false)
+            )
+        }
+        Expression::Binary(op, left, right) => {
+            let prec = precedence(op);
+            let right_assoc = is_right_associative(op);
+            format!(
+                "{} {} {}",
+                render_operand(left, prec, right_assoc, false),
+                operator_symbol(op),
+                render_operand(right, prec, right_assoc, true)
+            )
+        }
+        Expression::Assign(name, rhs) => format!("{} = {}", name, render(rhs)),
+    }
+}
+
+/// Render `expr` as an operand of a parent operator with precedence
+/// `parent_prec`, wrapping it in parentheses only when omitting them
+/// would change the grouping the tree encodes.
+fn render_operand(
+    expr: &Expression,
+    parent_prec: u8,
+    parent_right_assoc: bool,
+    is_right_operand: bool,
+) -> String {
+    let child_prec = expr_precedence(expr);
+    let needs_parens = if is_right_operand == parent_right_assoc {
+        child_prec < parent_prec
+    } else {
+        child_prec <= parent_prec
+    };
+
+    let rendered = render(expr);
+    if needs_parens {
+        format!("({})", rendered)
+    } else {
+        rendered
+    }
+}
+
+// First I thought about creating the syntax error with an enum,
+// but they don't give back much information about the error and
+// where they happened. So I decided to implement them as structs.
+// #[derive(Debug, PartialEq)]
+// enum SyntaxError {
+//     Lexicon,
+//     Parser,
+// }
+
+/// Define a structure for the syntactic errors that
+/// can be found when parsing an input.
+#[derive(Debug)]
+pub struct SyntaxError {
+    message: String,
+    level: String,
+}
+/// Define how to deal with the possible syntactic errors.
+impl SyntaxError {
+    /// Error in the lexicon for when a symbol cannot be found.
+    fn new_lex_error(message: String) -> Self {
+        SyntaxError {
+            message,
+            level: "Lexicon".to_string(),
+        }
+    }
+    /// Error while parsing the input.
+    fn new_parse_error(message: String) -> Self {
+        SyntaxError {
+            message,
+            level: "Parse".to_string(),
+        }
+    }
+    /// Error while evaluating a parsed expression.
+    fn new_eval_error(message: String) -> Self {
+        SyntaxError {
+            message,
+            level: "Eval".to_string(),
+        }
+    }
+}
+
+/// Pretty printing the errors.
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} Error {}", self.level, self.message)
+    }
+}
+
+/// And the error trait.
+impl Error for SyntaxError {}
+
+/// The structure of the parser to interpret the input.
+/// It consists on a Peekable type, so it's possible to
+/// peek the next element in the iterator without consuming it.
+struct Parser<'a> {
+    iter: &'a mut Peekable<Iter<'a, Token>>,
+}
+
+/// Define a top-down implementation of a `recursive descent parser`.
+impl<'a> Parser<'a> {
+    fn new(iter: &'a mut Peekable<Iter<'a, Token>>) -> Self {
+        Parser { iter }
+    }
+
+    /// Assert if there is a problem with the next value in the iterator.
+    fn assert_next(&mut self, token: Token) -> Result<(), SyntaxError> {
+        let next = self.iter.next();
+        if next.is_none() {
+            return Err(SyntaxError::new_parse_error(
+                "End of input unexpected".to_string(),
+            ));
+        }
+
+        if *next.unwrap() != token {
+            return Err(SyntaxError::new_parse_error(format!(
+                "Expected {:?} but actual {:?}",
+                token,
+                next.unwrap(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Consume and return the next token, erroring instead of panicking
+    /// if the input ended unexpectedly.
+    fn next_token(&mut self) -> Result<&'a Token, SyntaxError> {
+        self.iter
+            .next()
+            .ok_or_else(|| SyntaxError::new_parse_error("End of input unexpected".to_string()))
+    }
+
+    /// Peek at the next token without consuming it, erroring instead of
+    /// panicking if the input ended unexpectedly.
+    fn peek_token(&mut self) -> Result<&Token, SyntaxError> {
+        self.iter
+            .peek()
+            .copied()
+            .ok_or_else(|| SyntaxError::new_parse_error("End of input unexpected".to_string()))
+    }
+
+    /// Evaluate the expression. This is the lowest precedence level,
+    /// it just delegates to `term`.
+    fn expression(&mut self) -> Result<Expression, SyntaxError> {
+        self.term()
+    }
+
+    /// Handle `+` and `-`, delegating to `factor` for the
+    /// higher-precedence operators.
+    fn term(&mut self) -> Result<Expression, SyntaxError> {
+        let mut expr: Expression = self.factor()?;
+
+        loop {
+            let next = self.peek_token()?;
+            match next {
+                Token::Plus => {
+                    self.iter.next();
+                    let rhs = self.factor()?;
+                    expr = Expression::Binary(Operator::Add, Box::new(expr), Box::new(rhs));
+                }
+                Token::Dash => {
+                    self.iter.next();
+                    let rhs = self.factor()?;
+                    expr = Expression::Binary(Operator::Subtract, Box::new(expr), Box::new(rhs));
+                }
+                _ => break,
+            };
+        }
+
+        Ok(expr)
+    }
+
+    /// Handle `*` and `/`, which bind tighter than `+`/`-`, delegating
+    /// to `power` for the operands.
+    fn factor(&mut self) -> Result<Expression, SyntaxError> {
+        let mut expr: Expression = self.power()?;
+
+        loop {
+            let next = self.peek_token()?;
+            match next {
+                Token::Star => {
+                    self.iter.next();
+                    let rhs = self.power()?;
+                    expr = Expression::Binary(Operator::Multiply, Box::new(expr), Box::new(rhs));
+                }
+                Token::Slash => {
+                    self.iter.next();
+                    let rhs = self.power()?;
+                    expr = Expression::Binary(Operator::Divide, Box::new(expr), Box::new(rhs));
+                }
+                _ => break,
+            };
+        }
+
+        Ok(expr)
+    }
+
+    /// Handle `^`, which binds tighter than `*`/`/`. Recurses into
+    /// itself (rather than looping) for the right-hand side so that
+    /// the operator is right-associative, e.g. `2^3^2` is `2^(3^2)`.
+    fn power(&mut self) -> Result<Expression, SyntaxError> {
+        let expr = self.primary_expression()?;
+
+        let next = self.peek_token()?;
+        if *next == Token::Caret {
+            self.iter.next();
+            let rhs = self.power()?;
+            return Ok(Expression::Binary(
+                Operator::Power,
+                Box::new(expr),
+                Box::new(rhs),
+            ));
+        }
+
+        Ok(expr)
+    }
+
+    /// Evaluate numbers, parenthesis and minus signs `-`, and discard
+    /// not known tokens.
+    fn primary_expression(&mut self) -> Result<Expression, SyntaxError> {
+        let next = self.next_token()?;
+
+        match next {
+            Token::Number(n) => Ok(Expression::Number(*n)),
+            Token::LeftParen => {
+                let expr = self.expression()?;
+                self.assert_next(Token::RightParen)?;
+                Ok(expr)
+            }
+            Token::Dash => {
+                let expr = self.primary_expression()?;
+                Ok(Expression::Unary(Operator::Negative, Box::new(expr)))
+            }
+            Token::Identifier(name) => {
+                let name = name.clone();
+                if *self.peek_token()? == Token::Equals {
+                    self.iter.next();
+                    let rhs = self.expression()?;
+                    Ok(Expression::Assign(name, Box::new(rhs)))
+                } else {
+                    Ok(Expression::Variable(name))
+                }
+            }
+            _ => Err(SyntaxError::new_parse_error(format!(
+                "Unexpected token {:?}",
+                next
+            ))),
+        }
+    }
+
+    /// Parse the expression creating an abstract syntax tree.
+    fn parse(&mut self) -> Result<Expression, SyntaxError> {
+        let ast = self.expression()?;
+        self.assert_next(Token::End)?;
+        Ok(ast)
+    }
+}
+
+/// Define a lexicon to map the custom symbols from the problem to
+/// the actual meaning they should have.
+pub fn lexicon(expression: String) -> Result<Vec<Token>, SyntaxError> {
+    let mut iter = expression.chars().peekable();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut leftover: Option<char> = None;
+
+    loop {
+        let ch = match leftover {
+            Some(ch) => ch,
+            None => match iter.next() {
+                None => break,
+                Some(ch) => ch,
+            },
+        };
+        leftover = None;
+        match ch {
+            ' ' => continue,
+            'a' => tokens.push(Token::Plus),
+            'b' => tokens.push(Token::Dash),
+            'c' => tokens.push(Token::Star),
+            'd' => tokens.push(Token::Slash),
+            'e' => tokens.push(Token::LeftParen),
+            'f' => tokens.push(Token::RightParen),
+            'g' => tokens.push(Token::Caret),
+            '=' => tokens.push(Token::Equals),
+            ch if ch.is_ascii_digit() => {
+                let mut seen_dot = false;
+                let number_stream: String = iter
+                    .by_ref()
+                    .take_while(|c| match c {
+                        c if c.is_ascii_digit() => true,
+                        '.' if !seen_dot => {
+                            seen_dot = true;
+                            true
+                        }
+                        c => {
+                            leftover = Some(*c);
+                            false
+                        }
+                    })
+                    .collect();
+                let number: f64 = format!("{}{}", ch, number_stream).parse().unwrap();
+                tokens.push(Token::Number(number));
+            }
+            // Like the operators above, variable names are a single
+            // letter: the lexicon has no way to tell where one token
+            // ends and the next begins, so e.g. `xc2` must stay
+            // readable as `x`, `c` (Star), `2` rather than being
+            // swallowed into one multi-letter identifier.
+            ch if ch.is_ascii_alphabetic() => tokens.push(Token::Identifier(ch.to_string())),
+            _ => {
+                return Err(SyntaxError::new_lex_error(format!(
+                    "Unrecognized character {}. Skipping it.",
+                    ch
+                )))
+            }
+        }
+    }
+    tokens.push(Token::End);
+
+    Ok(tokens)
+}
+
+/// Parse a token stream, as produced by `lexicon`, into an
+/// `Expression` abstract syntax tree.
+pub fn parse(tokens: &[Token]) -> Result<Expression, SyntaxError> {
+    let mut token_iter = tokens.iter().peekable();
+    let mut parser = Parser::new(&mut token_iter);
+    parser.parse()
+}
+
+// Type alias some types so it's easier to write and read the code
+pub type Output = Result<f64, Box<dyn Error>>;
+pub type Input = String;
+
+/// Helper function to encapsulate the logic of parsing an
+/// expression, evaluating it and returning it.
+///
+/// `env` carries variable bindings across calls, so assigning a
+/// variable in one expression makes it visible to the next one
+/// evaluated with the same `env`.
+pub fn eval_expr(expression: Input, env: &mut HashMap<String, f64>) -> Output {
+    let tokens = lexicon(expression)?;
+    let mut ast = parse(&tokens)?;
+    ast.eval(env).map_err(|e| Box::new(e) as Box<dyn Error>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Smoke test to see if basic parsing works.
+    #[test]
+    fn test_dummy() {
+        let mut env = HashMap::new();
+        let res = eval_expr("7".to_string(), &mut env);
+        assert!(res.unwrap() == 7.0);
+
+        let res = eval_expr("b1".to_string(), &mut env);
+        assert!(res.unwrap() == -1.0);
+    }
+
+    /// Malformed or truncated input must return an `Err`, never panic.
+    #[test]
+    fn test_malformed_input_does_not_panic() {
+        let mut env = HashMap::new();
+        assert!(eval_expr("".to_string(), &mut env).is_err());
+        assert!(eval_expr("3a".to_string(), &mut env).is_err());
+        assert!(eval_expr("e3".to_string(), &mut env).is_err());
+    }
+
+    /// `^` binds tighter than `*`/`/` and is right-associative.
+    #[test]
+    fn test_power() {
+        let mut env = HashMap::new();
+        let res = eval_expr("2g3g2".to_string(), &mut env);
+        assert!(res.unwrap() == 512.0);
+
+        let res = eval_expr("2c3g2".to_string(), &mut env);
+        assert!(res.unwrap() == 18.0);
+
+        let res = eval_expr("2gb1".to_string(), &mut env);
+        assert!(res.unwrap() == 0.5);
+
+        let res = eval_expr("0gb2".to_string(), &mut env);
+        assert!(res.is_err());
+    }
+
+    /// Floating-point literals and true (non-truncating) division.
+    #[test]
+    fn test_floats() {
+        let mut env = HashMap::new();
+        let res = eval_expr("3.5a2".to_string(), &mut env);
+        assert!(res.unwrap() == 5.5);
+
+        let res = eval_expr("5d2".to_string(), &mut env);
+        assert!(res.unwrap() == 2.5);
+
+        let res = eval_expr("4d0".to_string(), &mut env);
+        assert!(res.is_err());
+    }
+
+    /// Variables are bound by `=` and can be referenced in later
+    /// expressions evaluated against the same environment.
+    #[test]
+    fn test_variables() {
+        let mut env = HashMap::new();
+        let res = eval_expr("x=3a4".to_string(), &mut env);
+        assert!(res.unwrap() == 7.0);
+
+        let res = eval_expr("xc2".to_string(), &mut env);
+        assert!(res.unwrap() == 14.0);
+
+        let res = eval_expr("y".to_string(), &mut env);
+        assert!(res.is_err());
+    }
+
+    /// Binary operators evaluate their left operand before their right
+    /// one, so an assignment on the left is visible to a variable
+    /// reference on the right of the same expression.
+    #[test]
+    fn test_eval_order_is_left_to_right() {
+        let mut env = HashMap::new();
+        let res = eval_expr("ex=5fdx".to_string(), &mut env);
+        assert!(res.unwrap() == 1.0);
+    }
+
+    /// Test using the given values in the problem.
+    #[test]
+    fn testing_data() {
+        let mut env = HashMap::new();
+        let res = eval_expr("3a2c4".to_string(), &mut env);
+        assert!(res.unwrap() == 11.0);
+
+        let res = eval_expr("32a2d2".to_string(), &mut env);
+        assert!(res.unwrap() == 33.0);
+
+        let res = eval_expr("500a10b66c32".to_string(), &mut env);
+        assert!(res.unwrap() == -1602.0);
+
+        let res = eval_expr("3ae4c66fb32".to_string(), &mut env);
+        assert!(res.unwrap() == 235.0);
+
+        let res = eval_expr("3c4d2aee2a4c41fc4f".to_string(), &mut env);
+        assert!(res.unwrap() == 670.0);
+    }
+
+    /// The AST renders through `Display` as infix with minimal parentheses.
+    #[test]
+    fn test_display_infix() {
+        let tokens = lexicon("3a2c4".to_string()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        assert_eq!(ast.to_string(), "3 + 2 * 4");
+
+        let tokens = lexicon("2g3g2".to_string()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        assert_eq!(ast.to_string(), "2 ^ 3 ^ 2");
+
+        let tokens = lexicon("e3a4fc2".to_string()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        assert_eq!(ast.to_string(), "(3 + 4) * 2");
+    }
+}